@@ -1,9 +1,11 @@
 use clap::{Arg, ArgAction, Command};
+use rayon::prelude::*;
 use std::error::Error;
 use std::fs::File;
 use std::io::{self, BufRead, BufReader};
+use unicode_segmentation::UnicodeSegmentation;
 
-type MyResult<T> = Result<T, Box<dyn Error>>;
+type MyResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
 
 #[derive(Debug)]
 pub struct Config {
@@ -12,6 +14,9 @@ pub struct Config {
     words: bool,
     bytes: bool,
     chars: bool,
+    max_line: bool,
+    files0_from: Option<String>,
+    graphemes: bool,
 }
 
 #[derive(Debug, PartialEq)]
@@ -20,6 +25,8 @@ pub struct FileInfo {
     num_words: usize,
     num_bytes: usize,
     num_chars: usize,
+    max_line_length: usize,
+    num_graphemes: usize,
 }
 
 pub fn get_args() -> MyResult<Config> {
@@ -32,7 +39,14 @@ pub fn get_args() -> MyResult<Config> {
                 .value_name("FILES")
                 .help("Input file(s)")
                 .num_args(1..)
-                .default_value("-"),
+                .default_value("-")
+                .conflicts_with("files0_from"),
+        )
+        .arg(
+            Arg::new("files0_from")
+                .value_name("FILE")
+                .help("Read input from the files specified by NUL-terminated names in FILE")
+                .long("files0-from"),
         )
         .arg(
             Arg::new("lines")
@@ -67,14 +81,31 @@ pub fn get_args() -> MyResult<Config> {
                 .action(ArgAction::SetTrue)
                 .conflicts_with("bytes"),
         )
+        .arg(
+            Arg::new("max_line")
+                .value_name("MAX_LINE_LENGTH")
+                .help("Show length of longest line")
+                .short('L')
+                .long("max-line-length")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("graphemes")
+                .value_name("GRAPHEMES")
+                .help("Show grapheme cluster count")
+                .long("graphemes")
+                .action(ArgAction::SetTrue),
+        )
         .get_matches();
 
     let mut lines = matches.get_flag("lines");
     let mut words = matches.get_flag("words");
     let mut bytes = matches.get_flag("bytes");
     let chars = matches.get_flag("chars");
+    let max_line = matches.get_flag("max_line");
+    let graphemes = matches.get_flag("graphemes");
 
-    if !lines && !words && !bytes && !chars {
+    if !lines && !words && !bytes && !chars && !max_line && !graphemes {
         lines = true;
         words = true;
         bytes = true;
@@ -90,6 +121,9 @@ pub fn get_args() -> MyResult<Config> {
         words: words,
         bytes: bytes,
         chars: chars,
+        max_line,
+        files0_from: matches.get_one("files0_from").cloned(),
+        graphemes,
     })
 }
 
@@ -98,42 +132,51 @@ pub fn run(config: Config) -> MyResult<()> {
     let mut total_words: usize = 0;
     let mut total_bytes: usize = 0;
     let mut total_chars: usize = 0;
+    let mut total_max_line_length: usize = 0;
+    let mut total_graphemes: usize = 0;
+
+    let files = resolve_files(&config)?;
+    let results = count_files_parallel(&files, &config);
 
     let mut file_count = 0;
-    for filename in &config.files {
+    for (i, result) in results {
+        let filename = &files[i];
         file_count += 1;
-        match open(filename) {
+        match result {
+            Ok(file_info) => {
+                total_lines = total_lines + file_info.num_lines;
+                total_words = total_words + file_info.num_words;
+                total_bytes = total_bytes + file_info.num_bytes;
+                total_chars = total_chars + file_info.num_chars;
+                total_max_line_length = total_max_line_length.max(file_info.max_line_length);
+                total_graphemes += file_info.num_graphemes;
+                println!(
+                    "{}{}{}{}{}{}{}",
+                    format_field(file_info.num_lines, config.lines),
+                    format_field(file_info.num_words, config.words),
+                    format_field(file_info.num_bytes, config.bytes),
+                    format_field(file_info.num_chars, config.chars),
+                    format_field(file_info.max_line_length, config.max_line),
+                    format_field(file_info.num_graphemes, config.graphemes),
+                    if filename == "-" {
+                        "".to_string()
+                    } else {
+                        format!(" {}", filename)
+                    }
+                );
+            }
             Err(err) => eprintln!("{}: {}", filename, err),
-            Ok(f) => match count(f) {
-                Ok(file_info) => {
-                    total_lines = total_lines + file_info.num_lines;
-                    total_words = total_words + file_info.num_words;
-                    total_bytes = total_bytes + file_info.num_bytes;
-                    total_chars = total_chars + file_info.num_chars;
-                    println!(
-                        "{}{}{}{}{}",
-                        format_field(file_info.num_lines, config.lines),
-                        format_field(file_info.num_words, config.words),
-                        format_field(file_info.num_bytes, config.bytes),
-                        format_field(file_info.num_chars, config.chars),
-                        if filename == "-" {
-                            "".to_string()
-                        } else {
-                            format!(" {}", filename)
-                        }
-                    );
-                }
-                Err(e2) => eprintln!("{}: {}", filename, e2),
-            },
         }
     }
     if file_count > 1 {
         println!(
-            "{}{}{}{} total",
+            "{}{}{}{}{}{} total",
             format_field(total_lines, config.lines),
             format_field(total_words, config.words),
             format_field(total_bytes, config.bytes),
-            format_field(total_chars, config.chars)
+            format_field(total_chars, config.chars),
+            format_field(total_max_line_length, config.max_line),
+            format_field(total_graphemes, config.graphemes)
         );
     }
     Ok(())
@@ -154,41 +197,169 @@ fn open(filename: &str) -> MyResult<Box<dyn BufRead>> {
     }
 }
 
-pub fn count(mut file: impl BufRead) -> MyResult<FileInfo> {
+/// Resolves the list of files to count: either `config.files` or, when
+/// `--files0-from` was given, the NUL-separated names read from that
+/// source (a file, or stdin when the value is `-`).
+fn resolve_files(config: &Config) -> MyResult<Vec<String>> {
+    match &config.files0_from {
+        Some(source) => read_files0_from(source),
+        None => Ok(config.files.clone()),
+    }
+}
+
+fn read_files0_from(source: &str) -> MyResult<Vec<String>> {
+    let mut contents = String::new();
+    open(source)
+        .map_err(|e| format!("{source}: {e}"))?
+        .read_to_string(&mut contents)?;
+    let mut names: Vec<&str> = contents.split('\0').collect();
+    if names.last() == Some(&"") {
+        names.pop();
+    }
+    if names.iter().any(|name| name.is_empty()) {
+        return Err(format!("{source}: invalid zero-length file name").into());
+    }
+    Ok(names.into_iter().map(str::to_string).collect())
+}
+
+/// Processes `files` concurrently, then returns the results paired with
+/// their original index and sorted back into that order so output stays
+/// deterministic regardless of completion order. A per-file error is
+/// carried in its slot rather than aborting the rest of the batch.
+fn count_files_parallel(files: &[String], config: &Config) -> Vec<(usize, MyResult<FileInfo>)> {
+    let mut results: Vec<(usize, MyResult<FileInfo>)> = files
+        .par_iter()
+        .enumerate()
+        .map(|(i, filename)| (i, process_file(filename, config)))
+        .collect();
+    results.sort_by_key(|(i, _)| *i);
+    results
+}
+
+/// Opens and counts `filename`, taking the fast metadata-only path when the
+/// config asks for nothing but the byte count.
+fn process_file(filename: &str, config: &Config) -> MyResult<FileInfo> {
+    if config.bytes
+        && !config.lines
+        && !config.words
+        && !config.chars
+        && !config.max_line
+        && !config.graphemes
+    {
+        let num_bytes = count_bytes(filename)?;
+        return Ok(FileInfo {
+            num_lines: 0,
+            num_words: 0,
+            num_bytes,
+            num_chars: 0,
+            max_line_length: 0,
+            num_graphemes: 0,
+        });
+    }
+    count(open(filename)?, config.graphemes)
+}
+
+/// Counts the bytes in `filename` without reading its contents when
+/// possible: a regular file's size is taken straight from its metadata.
+/// Stdin and files whose reported size can't be trusted (pipes, char
+/// devices, `/proc` entries reporting a size of 0) fall back to a
+/// streaming byte count.
+fn count_bytes(filename: &str) -> MyResult<usize> {
+    if filename != "-" {
+        if let Ok(metadata) = std::fs::metadata(filename) {
+            if metadata.is_file() && metadata.len() > 0 {
+                return Ok(metadata.len() as usize);
+            }
+        }
+    }
+    stream_count_bytes(open(filename)?)
+}
+
+fn stream_count_bytes(mut file: impl BufRead) -> MyResult<usize> {
+    let mut buf = [0u8; 8192];
+    let mut num_bytes = 0;
+    loop {
+        let bytes_read = file.read(&mut buf)?;
+        if bytes_read == 0 {
+            break;
+        }
+        num_bytes += bytes_read;
+    }
+    Ok(num_bytes)
+}
+
+pub fn count(mut file: impl BufRead, compute_graphemes: bool) -> MyResult<FileInfo> {
     let mut num_lines = 0;
+    let mut num_words = 0;
+    let mut num_bytes = 0;
+    let mut num_chars = 0;
+    let mut max_line_length = 0;
+    let mut num_graphemes = 0;
 
-    let mut buffer = String::new();
-    while file.read_line(&mut buffer)? > 0 {
+    let mut buffer: Vec<u8> = Vec::new();
+    loop {
+        buffer.clear();
+        let bytes_read = file.read_until(b'\n', &mut buffer)?;
+        if bytes_read == 0 {
+            break;
+        }
         num_lines += 1;
-    }
+        num_bytes += bytes_read;
 
-    let num_words = buffer.split_whitespace().count();
-    let num_bytes = buffer.as_bytes().len();
-    let num_chars = buffer.chars().count();
+        let line = String::from_utf8_lossy(&buffer);
+        num_words += line.split_whitespace().count();
+        num_chars += line.chars().count();
+        if compute_graphemes {
+            num_graphemes += line.graphemes(true).count();
+        }
+        max_line_length = max_line_length.max(line_display_width(line.trim_end_matches(['\n', '\r'])));
+    }
 
     Ok(FileInfo {
         num_lines,
         num_words,
         num_bytes,
         num_chars,
+        max_line_length,
+        num_graphemes,
     })
 }
 
+/// Computes the display width of a line, treating a tab as advancing to
+/// the next multiple of 8 columns. Does not account for wide or combining
+/// characters; adopting `unicode-width` would be a future improvement.
+fn line_display_width(line: &str) -> usize {
+    let mut width = 0;
+    for ch in line.chars() {
+        if ch == '\t' {
+            width += 8 - (width % 8);
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{count, FileInfo};
+    use super::{
+        count, count_bytes, count_files_parallel, read_files0_from, stream_count_bytes, Config,
+        FileInfo,
+    };
     use std::io::Cursor;
 
     #[test]
     fn test_count() {
         let text = "I don't want the world. I just want your half.\r\n";
-        let info = count(Cursor::new(text));
+        let info = count(Cursor::new(text), true);
         assert!(info.is_ok());
         let expected = FileInfo {
             num_lines: 1,
             num_words: 10,
             num_chars: 48,
             num_bytes: 48,
+            max_line_length: 46,
+            num_graphemes: 47,
         };
         assert_eq!(info.unwrap(), expected);
     }
@@ -197,14 +368,113 @@ mod tests {
     fn test_count_2() {
         let text = "I don't want the world. I just want your half.\r\n\
         I don't want the world. I just want your half.\r\n";
-        let info = count(Cursor::new(text));
+        let info = count(Cursor::new(text), true);
         assert!(info.is_ok());
         let expected = FileInfo {
             num_lines: 2,
             num_words: 20,
             num_chars: 96,
             num_bytes: 96,
+            max_line_length: 46,
+            num_graphemes: 94,
         };
         assert_eq!(info.unwrap(), expected);
     }
+
+    #[test]
+    fn test_count_bytes_regular_file_uses_metadata() {
+        let path = std::env::temp_dir().join("wcr_test_count_bytes_regular.txt");
+        std::fs::write(&path, b"hello world").unwrap();
+        let result = count_bytes(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(result.unwrap(), 11);
+    }
+
+    #[test]
+    fn test_count_bytes_empty_file_falls_back_to_streaming() {
+        let path = std::env::temp_dir().join("wcr_test_count_bytes_empty.txt");
+        std::fs::write(&path, b"").unwrap();
+        let result = count_bytes(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(result.unwrap(), 0);
+    }
+
+    #[test]
+    fn test_stream_count_bytes_non_regular_source() {
+        let bytes: &[u8] = &[0xff, 0xfe, 0x00, 0x41, 0x42];
+        let result = stream_count_bytes(Cursor::new(bytes));
+        assert_eq!(result.unwrap(), 5);
+    }
+
+    #[test]
+    fn test_count_files_parallel_preserves_order_and_isolates_errors() {
+        let path = std::env::temp_dir().join("wcr_test_parallel_ok.txt");
+        std::fs::write(&path, b"a b c\n").unwrap();
+        let config = Config {
+            files: vec![],
+            lines: true,
+            words: true,
+            bytes: true,
+            chars: false,
+            max_line: false,
+            files0_from: None,
+            graphemes: false,
+        };
+        let files = vec![
+            path.to_str().unwrap().to_string(),
+            "wcr_test_parallel_missing_nonexistent.txt".to_string(),
+            path.to_str().unwrap().to_string(),
+        ];
+
+        let results = count_files_parallel(&files, &config);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].0, 0);
+        assert!(results[0].1.is_ok());
+        assert_eq!(results[1].0, 1);
+        assert!(results[1].1.is_err());
+        assert_eq!(results[2].0, 2);
+        assert!(results[2].1.is_ok());
+    }
+
+    #[test]
+    fn test_read_files0_from_splits_on_nul_and_skips_trailing_empty() {
+        let path = std::env::temp_dir().join("wcr_test_files0_from.txt");
+        std::fs::write(&path, b"one.txt\0two.txt\0three.txt\0").unwrap();
+        let names = read_files0_from(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            names.unwrap(),
+            vec!["one.txt".to_string(), "two.txt".to_string(), "three.txt".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_read_files0_from_errors_on_embedded_empty_name() {
+        let path = std::env::temp_dir().join("wcr_test_files0_from_embedded_empty.txt");
+        std::fs::write(&path, b"one.txt\0\0two.txt\0").unwrap();
+        let result = read_files0_from(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_read_files0_from_missing_source_reports_source_name() {
+        let err = read_files0_from("wcr_test_files0_from_missing_nonexistent.txt").unwrap_err();
+        assert!(err.to_string().contains("wcr_test_files0_from_missing_nonexistent.txt"));
+    }
+
+    #[test]
+    fn test_count_graphemes_vs_chars_for_combining_sequences() {
+        // "e" + a combining acute accent, and a thumbs-up emoji + a skin-tone
+        // modifier: each is two Unicode scalar values but a single
+        // user-perceived character (extended grapheme cluster).
+        let text = "e\u{0301}\r\n\u{1F44D}\u{1F3FD}\r\n";
+        let info = count(Cursor::new(text), true);
+        assert!(info.is_ok());
+        let info = info.unwrap();
+        assert_eq!(info.num_chars, 8);
+        assert_eq!(info.num_graphemes, 4);
+    }
 }